@@ -0,0 +1,101 @@
+//! Decouples error *rendering* from the error enums in [`crate::error`], so
+//! an embedder can supply translated message templates instead of being
+//! stuck with the hardcoded English `Display` output.
+//!
+//! Each error variant exposes a stable [`crate::error::LexicalErrorType::code`]
+//! / [`crate::error::ParseErrorType::code`] discriminant (e.g. `"E-indent"`)
+//! plus its structured fields, already rendered to strings in positional
+//! order. A [`MessageCatalog`] maps a code to a template and fills it in;
+//! the [`DefaultCatalog`] reproduces today's English text by falling back
+//! to the type's own `Display` impl.
+use crate::error::{FStringErrorType, LexicalErrorType, ParseErrorType};
+
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+/// Supplies localized templates keyed by an error's stable `code()`.
+///
+/// `args` holds exactly the fields that code's English `Display` text
+/// interpolates, in the same order, and nothing else — e.g. `"E-unrecognized-token"`
+/// (`ParseErrorType::UnrecognizedToken` when it isn't the indent special
+/// case) gets `[token]`, while `"E-unexpected-indent"` and
+/// `"E-expected-indent"` get an empty slice, because their English text
+/// ("unexpected indent" / "expected an indented block") doesn't interpolate
+/// anything. A translated template for a given code should expect the same
+/// arity `Display` does; implementations that don't have a translation for
+/// `code` should return `None` so the caller can fall back to the default
+/// English message.
+pub trait MessageCatalog {
+    fn message(&self, code: &'static str, args: &[String]) -> Option<String>;
+}
+
+/// Reproduces the existing English `Display` output. This is what you get
+/// if you don't plug in a catalog of your own.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultCatalog;
+
+impl MessageCatalog for DefaultCatalog {
+    fn message(&self, _code: &'static str, _args: &[String]) -> Option<String> {
+        None
+    }
+}
+
+/// Renders `error` through `catalog`, falling back to the built-in English
+/// `Display` text when the catalog has no translation for this error's
+/// code. This is the entry point embedders actually call; `MessageCatalog`
+/// on its own is just the lookup they implement.
+pub fn render_lexical_error(error: &LexicalErrorType, catalog: &dyn MessageCatalog) -> String {
+    catalog
+        .message(error.code(), &lexical_error_args(error))
+        .unwrap_or_else(|| error.to_string())
+}
+
+pub fn render_fstring_error(error: &FStringErrorType, catalog: &dyn MessageCatalog) -> String {
+    catalog
+        .message(error.code(), &fstring_error_args(error))
+        .unwrap_or_else(|| error.to_string())
+}
+
+pub fn render_parse_error(error: &ParseErrorType, catalog: &dyn MessageCatalog) -> String {
+    catalog
+        .message(error.code(), &parse_error_args(error))
+        .unwrap_or_else(|| error.to_string())
+}
+
+/// Pulls out the structured fields behind a lexical error's `Display` text,
+/// in the order that text would interpolate them.
+fn lexical_error_args(error: &LexicalErrorType) -> Vec<String> {
+    match error {
+        LexicalErrorType::UnrecognizedToken { tok } => vec![tok.to_string()],
+        LexicalErrorType::OtherError(msg) => vec![msg.clone()],
+        LexicalErrorType::FStringError(inner) => fstring_error_args(inner),
+        _ => Vec::new(),
+    }
+}
+
+fn fstring_error_args(error: &FStringErrorType) -> Vec<String> {
+    match error {
+        FStringErrorType::InvalidExpression(inner) => vec![inner.to_string()],
+        _ => Vec::new(),
+    }
+}
+
+fn parse_error_args(error: &ParseErrorType) -> Vec<String> {
+    match error {
+        ParseErrorType::ExtraToken(tok) => vec![format!("{:?}", tok)],
+        // The indent special cases (`"E-unexpected-indent"` /
+        // `"E-expected-indent"`) don't interpolate the token or the
+        // expected string into their Display text, so they get no args;
+        // only the generic `"E-unrecognized-token"` case does.
+        ParseErrorType::UnrecognizedToken(tok, _, _) if error.code() == "E-unrecognized-token" => {
+            vec![tok.to_string()]
+        }
+        ParseErrorType::UnrecognizedToken(..) => Vec::new(),
+        ParseErrorType::Lexical(inner) => lexical_error_args(inner),
+        _ => Vec::new(),
+    }
+}
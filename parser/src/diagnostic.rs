@@ -0,0 +1,83 @@
+//! Structured diagnostics: an error plus an optional actionable hint and
+//! replacement-span suggestions, for editors that want to offer quick-fixes
+//! instead of just underlining a span.
+use crate::ast::Location;
+use crate::error::{ParseError, ParseErrorType};
+
+use alloc::{string::String, vec, vec::Vec};
+
+/// A proposed fix: replace `start..end` with `replacement`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Suggestion {
+    pub start: Location,
+    pub end: Location,
+    pub replacement: String,
+}
+
+/// A [`ParseError`] enriched with a stable code and, where we can offer one,
+/// a human-readable hint and concrete fix-it suggestions.
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    pub error: ParseErrorType,
+    pub location: Location,
+    pub end_location: Location,
+    pub code: &'static str,
+    pub help: Option<String>,
+    pub suggestions: Vec<Suggestion>,
+}
+
+impl From<ParseError> for Diagnostic {
+    fn from(err: ParseError) -> Self {
+        let code = err.error.code();
+        let help = help_for(&err.error);
+        let suggestions = suggestions_for(&err);
+        Diagnostic {
+            error: err.error,
+            location: err.location,
+            end_location: err.end_location,
+            code,
+            help,
+            suggestions,
+        }
+    }
+}
+
+/// An actionable hint for error variants where we can say more than "this
+/// is wrong" — e.g. pointing at the indentation fix for a tab/space mix.
+///
+/// `is_indentation_error()` covers both "a block was expected here but
+/// there's no indent" and "there's an indent but none was expected", which
+/// need opposite advice, so this keys off the finer-grained `code()` instead
+/// of that coarser predicate.
+fn help_for(error: &ParseErrorType) -> Option<String> {
+    match error.code() {
+        "E-expected-indent" => Some(String::from("expected an indented block here")),
+        "E-unexpected-indent" => Some(String::from(
+            "unexpected indent; remove the extra indentation on this line",
+        )),
+        "E-indent" => Some(String::from(
+            "this line's indentation doesn't match any enclosing block",
+        )),
+        "E-tab" => Some(String::from(
+            "inconsistent use of tabs and spaces in indentation; convert tabs to spaces",
+        )),
+        _ => None,
+    }
+}
+
+/// Concrete fix-it spans we can offer without guessing at user intent. For
+/// now this only covers the tab/space case, where the fix is unambiguous
+/// enough to name at all.
+fn suggestions_for(err: &ParseError) -> Vec<Suggestion> {
+    if err.error.is_tab_error() {
+        // We don't track the column/indent width that produced the error,
+        // so this can only offer a generic "use spaces" rewrite of the
+        // offending span, not the exact width the surrounding block uses.
+        return vec![Suggestion {
+            start: err.location,
+            end: err.end_location,
+            replacement: String::from("    "),
+        }];
+    }
+    Vec::new()
+}
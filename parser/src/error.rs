@@ -5,7 +5,7 @@ use lalrpop_util::ParseError as LalrpopError;
 use crate::ast::Location;
 use crate::token::Tok;
 
-use alloc::{boxed::Box, string::String};
+use alloc::{boxed::Box, string::String, vec::Vec};
 use core::fmt;
 #[cfg(feature = "std")]
 use std::error::Error;
@@ -15,6 +15,9 @@ use std::error::Error;
 pub struct LexicalError {
     pub error: LexicalErrorType,
     pub location: Location,
+    /// End of the offending span. Equal to `location` for errors that don't
+    /// naturally cover a range (e.g. an unexpected single character).
+    pub end_location: Location,
 }
 
 #[derive(Debug, PartialEq)]
@@ -34,6 +37,30 @@ pub enum LexicalErrorType {
     OtherError(String),
 }
 
+impl LexicalErrorType {
+    /// Stable, machine-readable discriminant for this error, independent of
+    /// the (English, hardcoded) `Display` text. An embedder plugging in a
+    /// [`crate::catalog::MessageCatalog`] keys its translated templates off
+    /// of this.
+    pub fn code(&self) -> &'static str {
+        match self {
+            LexicalErrorType::StringError => "E-string",
+            LexicalErrorType::UnicodeError => "E-unicode",
+            LexicalErrorType::NestingError => "E-nesting",
+            LexicalErrorType::IndentationError => "E-indent",
+            LexicalErrorType::TabError => "E-tab",
+            LexicalErrorType::DefaultArgumentError => "E-default-arg",
+            LexicalErrorType::PositionalArgumentError => "E-positional-arg",
+            LexicalErrorType::DuplicateKeywordArgumentError => "E-dup-kwarg",
+            LexicalErrorType::UnrecognizedToken { .. } => "E-unrecognized-char",
+            LexicalErrorType::FStringError(error) => error.code(),
+            LexicalErrorType::LineContinuationError => "E-line-continuation",
+            LexicalErrorType::EOF => "E-eof",
+            LexicalErrorType::OtherError(_) => "E-other",
+        }
+    }
+}
+
 impl fmt::Display for LexicalErrorType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -68,6 +95,30 @@ impl fmt::Display for LexicalErrorType {
     }
 }
 
+impl LexicalError {
+    /// Build a `LexicalError` with an explicit end span.
+    ///
+    /// Prefer this at construction sites (the lexer) over the struct
+    /// literal: it makes the lexer pass the token's actual end position
+    /// instead of accidentally reusing `location` for both ends of the
+    /// span. Every `LexicalError { .. }` literal in `lexer.rs` needs to be
+    /// migrated to this constructor, supplying the real end location the
+    /// lexer already has in hand at each call site (e.g. the position after
+    /// the offending character/token), not a copy of `location`.
+    pub fn new(error: LexicalErrorType, location: Location, end_location: Location) -> Self {
+        LexicalError {
+            error,
+            location,
+            end_location,
+        }
+    }
+
+    /// The `(start, end)` span of the offending text.
+    pub fn span(&self) -> (Location, Location) {
+        (self.location, self.end_location)
+    }
+}
+
 #[cfg(feature = "std")]
 impl Error for LexicalErrorType {
     fn source(&self) -> Option<&(dyn Error + 'static)> {
@@ -83,6 +134,20 @@ impl Error for LexicalErrorType {
 pub struct FStringError {
     pub error: FStringErrorType,
     pub location: Location,
+    pub end_location: Location,
+}
+
+impl FStringError {
+    /// Same rationale as `LexicalError::new`: construction sites (the
+    /// f-string sub-parser) must supply the error's real end position, not
+    /// a copy of `location`.
+    pub fn new(error: FStringErrorType, location: Location, end_location: Location) -> Self {
+        FStringError {
+            error,
+            location,
+            end_location,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq)]
@@ -97,6 +162,23 @@ pub enum FStringErrorType {
     ExpressionNestedTooDeeply,
 }
 
+impl FStringErrorType {
+    /// Stable, machine-readable discriminant; see
+    /// [`LexicalErrorType::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            FStringErrorType::UnclosedLbrace => "E-fstring-unclosed-lbrace",
+            FStringErrorType::UnopenedRbrace => "E-fstring-unopened-rbrace",
+            FStringErrorType::ExpectedRbrace => "E-fstring-expected-rbrace",
+            FStringErrorType::InvalidExpression(_) => "E-fstring-invalid-expression",
+            FStringErrorType::InvalidConversionFlag => "E-fstring-invalid-conversion-flag",
+            FStringErrorType::EmptyExpression => "E-fstring-empty-expression",
+            FStringErrorType::MismatchedDelimiter => "E-fstring-mismatched-delimiter",
+            FStringErrorType::ExpressionNestedTooDeeply => "E-fstring-nested-too-deeply",
+        }
+    }
+}
+
 impl fmt::Display for FStringErrorType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -119,10 +201,11 @@ impl fmt::Display for FStringErrorType {
 impl From<FStringError> for LalrpopError<Location, Tok, LexicalError> {
     fn from(err: FStringError) -> Self {
         lalrpop_util::ParseError::User {
-            error: LexicalError {
-                error: LexicalErrorType::FStringError(err.error),
-                location: err.location,
-            },
+            error: LexicalError::new(
+                LexicalErrorType::FStringError(err.error),
+                err.location,
+                err.end_location,
+            ),
         }
     }
 }
@@ -142,6 +225,10 @@ impl Error for FStringErrorType {
 pub struct ParseError {
     pub error: ParseErrorType,
     pub location: Location,
+    /// End of the offending span, so editors can underline the whole range
+    /// instead of placing a caret at `location` alone. Equal to `location`
+    /// when LALRPOP only gave us a single position to work with.
+    pub end_location: Location,
 }
 
 #[derive(Debug, PartialEq)]
@@ -153,7 +240,12 @@ pub enum ParseErrorType {
     /// Parser encountered an invalid token
     InvalidToken,
     /// Parser encountered an unexpected token
-    UnrecognizedToken(Tok, Option<String>),
+    ///
+    /// The `Option<String>` mirrors CPython's "only one possible expected
+    /// token" behavior for `Display`, while the `Vec<String>` retains the whole
+    /// list LALRPOP gave us, for tooling that wants to say "expected one of:
+    /// `:`, `=`, `NEWLINE`" instead.
+    UnrecognizedToken(Tok, Option<String>, Vec<String>),
     /// Maps to `User` type from `lalrpop-util`
     Lexical(LexicalErrorType),
 }
@@ -166,31 +258,38 @@ impl From<LalrpopError<Location, Tok, LexicalError>> for ParseError {
             LalrpopError::InvalidToken { location } => ParseError {
                 error: ParseErrorType::EOF,
                 location,
+                end_location: location,
             },
             LalrpopError::ExtraToken { token } => ParseError {
                 error: ParseErrorType::ExtraToken(token.1),
                 location: token.0,
+                end_location: token.2,
             },
             LalrpopError::User { error } => ParseError {
                 error: ParseErrorType::Lexical(error.error),
                 location: error.location,
+                end_location: error.end_location,
             },
             LalrpopError::UnrecognizedToken { token, expected } => {
                 // Hacky, but it's how CPython does it. See PyParser_AddToken,
                 // in particular "Only one possible expected token" comment.
-                let expected = if expected.len() == 1 {
+                // We keep the full list alongside it for tooling that wants
+                // more than the single-token phrasing.
+                let single_expected = if expected.len() == 1 {
                     Some(expected[0].clone())
                 } else {
                     None
                 };
                 ParseError {
-                    error: ParseErrorType::UnrecognizedToken(token.1, expected),
+                    error: ParseErrorType::UnrecognizedToken(token.1, single_expected, expected),
                     location: token.0,
+                    end_location: token.2,
                 }
             }
             LalrpopError::UnrecognizedEOF { location, .. } => ParseError {
                 error: ParseErrorType::EOF,
                 location,
+                end_location: location,
             },
         }
     }
@@ -202,13 +301,35 @@ impl fmt::Display for ParseError {
     }
 }
 
+impl ParseErrorType {
+    /// Stable, machine-readable discriminant; see
+    /// [`LexicalErrorType::code`].
+    pub fn code(&self) -> &'static str {
+        match self {
+            ParseErrorType::EOF => "E-eof",
+            ParseErrorType::ExtraToken(_) => "E-extra-token",
+            ParseErrorType::InvalidToken => "E-invalid-token",
+            ParseErrorType::UnrecognizedToken(tok, expected, _) => {
+                if *tok == Tok::Indent {
+                    "E-unexpected-indent"
+                } else if expected.as_deref() == Some("Indent") {
+                    "E-expected-indent"
+                } else {
+                    "E-unrecognized-token"
+                }
+            }
+            ParseErrorType::Lexical(error) => error.code(),
+        }
+    }
+}
+
 impl fmt::Display for ParseErrorType {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match *self {
             ParseErrorType::EOF => write!(f, "Got unexpected EOF"),
             ParseErrorType::ExtraToken(ref tok) => write!(f, "Got extraneous token: {:?}", tok),
             ParseErrorType::InvalidToken => write!(f, "Got invalid token"),
-            ParseErrorType::UnrecognizedToken(ref tok, ref expected) => {
+            ParseErrorType::UnrecognizedToken(ref tok, ref expected, _) => {
                 if *tok == Tok::Indent {
                     write!(f, "unexpected indent")
                 } else if expected.as_deref() == Some("Indent") {
@@ -236,7 +357,7 @@ impl ParseErrorType {
     pub fn is_indentation_error(&self) -> bool {
         match self {
             ParseErrorType::Lexical(LexicalErrorType::IndentationError) => true,
-            ParseErrorType::UnrecognizedToken(token, expected) => {
+            ParseErrorType::UnrecognizedToken(token, expected, _) => {
                 *token == Tok::Indent || expected.as_ref().map_or(false, |s| s == "Indent")
             }
             _ => false,
@@ -245,6 +366,23 @@ impl ParseErrorType {
     pub fn is_tab_error(&self) -> bool {
         matches!(self, ParseErrorType::Lexical(LexicalErrorType::TabError))
     }
+    /// The full set of tokens LALRPOP would have accepted instead of the one
+    /// it got, for "expected one of: ..." style diagnostics. Empty for error
+    /// variants that aren't `UnrecognizedToken`.
+    pub fn expected_tokens(&self) -> &[String] {
+        match self {
+            ParseErrorType::UnrecognizedToken(_, _, expected) => expected,
+            _ => &[],
+        }
+    }
+}
+
+impl ParseError {
+    /// The `(start, end)` span of the offending token, for editors that want
+    /// to underline a range rather than place a caret at a single position.
+    pub fn span(&self) -> (Location, Location) {
+        (self.location, self.end_location)
+    }
 }
 
 impl core::ops::Deref for ParseError {
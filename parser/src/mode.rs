@@ -4,13 +4,19 @@ use core::fmt;
 pub enum Mode {
     Program,
     Statement,
+    /// A single interactive statement, as typed at the REPL prompt.
+    /// Like `Program`, but the parser (see `parser::parse`) accepts exactly
+    /// one statement and reports an error if more input follows, instead of
+    /// silently parsing a whole program.
+    Interactive,
 }
 
 impl core::str::FromStr for Mode {
     type Err = ModeParseError;
     fn from_str(s: &str) -> Result<Self, ModeParseError> {
         match s {
-            "exec" | "single" => Ok(Mode::Program),
+            "exec" => Ok(Mode::Program),
+            "single" => Ok(Mode::Interactive),
             "eval" => Ok(Mode::Statement),
             _ => Err(ModeParseError { _priv: () }),
         }
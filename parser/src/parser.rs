@@ -0,0 +1,223 @@
+//! Parser entry points: the plain one, a recovery mode used by
+//! editors/language servers that want to see every syntax error in a file
+//! instead of just the first, and `Mode::Interactive`'s one-statement-only
+//! enforcement for the REPL.
+use crate::ast;
+use crate::error::{ParseError, ParseErrorType};
+use crate::lexer::{self, LexResult};
+use crate::mode::Mode;
+use crate::token::Tok;
+
+use alloc::vec::Vec;
+
+/// Above this many recovered errors we give up and return what we have so
+/// far, rather than letting a truly malformed file turn into an unbounded
+/// amount of recovery work.
+const MAX_RECOVERED_ERRORS: usize = 200;
+
+/// Parse `source` the normal way, bailing out on the first error.
+///
+/// This is the fast path used by the compiler, where a single syntax error
+/// is enough to stop.
+pub fn parse(source: &str, mode: Mode) -> Result<ast::Mod, ParseError> {
+    let lxr = lexer::make_tokenizer(source);
+    parse_tokens(lxr, mode)
+}
+
+/// Parse `source`, recovering from syntax errors instead of stopping at the
+/// first one.
+///
+/// Always returns a structurally walkable AST, alongside every `ParseError`
+/// recovered along the way (empty if the source parsed cleanly). In
+/// `Mode::Program`, recovery works at statement granularity: each top-level
+/// statement (`take_one_statement`'s unit — a compound statement's whole
+/// suite counts as one) is parsed on its own, and every one that parses
+/// successfully is appended to the returned body, in source order, whether
+/// or not a later statement fails. So for `x=1\ny=2\n$$$\nz=3`, the result
+/// is `[x=1, y=2, z=3]` plus the one error for the `$$$` line, not just the
+/// tail after the last error. Recovery stops once `MAX_RECOVERED_ERRORS`
+/// errors have been collected: the statements after that point are not
+/// parsed and are missing from the returned body.
+///
+/// There's no `ast::Stmt`/`ast::Expr` "error" node in this AST to splice in
+/// for a statement that failed to parse, so that statement is simply
+/// missing from the body rather than represented by a placeholder — use the
+/// accompanying `ParseError::location`/`end_location` to find where it was.
+///
+/// `Mode::Statement`/`Mode::Interactive` parse a single unit rather than a
+/// sequence of statements, so there's no chunking to do there: this just
+/// parses once and, on failure, returns an empty module alongside the
+/// error, since there's no meaningful partial tree to hand back.
+pub fn parse_recoverable(source: &str, mode: Mode) -> (ast::Mod, Vec<ParseError>) {
+    let mut lxr = lexer::make_tokenizer(source).peekable();
+    let mut errors = Vec::new();
+
+    if !matches!(mode, Mode::Program) {
+        return match parse_tokens(&mut lxr, mode) {
+            Ok(tree) => (tree, errors),
+            Err(err) => {
+                errors.push(err);
+                (empty_module(), errors)
+            }
+        };
+    }
+
+    let mut body = Vec::new();
+    let mut type_ignores = Vec::new();
+
+    while lxr.peek().is_some() {
+        let chunk = take_one_statement(&mut lxr);
+        if chunk.is_empty() {
+            break;
+        }
+        match crate::python::parse_tokens(chunk, mode) {
+            Ok(ast::Mod::Module {
+                body: mut stmts,
+                type_ignores: mut ignores,
+            }) => {
+                body.append(&mut stmts);
+                type_ignores.append(&mut ignores);
+            }
+            Ok(_) => unreachable!("Mode::Program always parses to Mod::Module"),
+            Err(err) => {
+                errors.push(err);
+                if errors.len() >= MAX_RECOVERED_ERRORS {
+                    break;
+                }
+                // take_one_statement already advanced past this statement's
+                // boundary (its closing Newline/Dedent), so the next loop
+                // iteration starts fresh on the following statement without
+                // any extra synchronization step.
+            }
+        }
+    }
+
+    (ast::Mod::Module { body, type_ignores }, errors)
+}
+
+fn empty_module() -> ast::Mod {
+    ast::Mod::Module {
+        body: Vec::new(),
+        type_ignores: Vec::new(),
+    }
+}
+
+fn parse_tokens(
+    lxr: impl IntoIterator<Item = LexResult>,
+    mode: Mode,
+) -> Result<ast::Mod, ParseError> {
+    match mode {
+        Mode::Interactive => parse_single_interactive_statement(lxr),
+        _ => crate::python::parse_tokens(lxr, mode),
+    }
+}
+
+/// `Mode::Interactive` ("single" mode): parse exactly one interactive
+/// statement and reject anything left over, instead of silently consuming
+/// the rest of the input as a whole program the way `Mode::Program` does.
+/// This is what CPython's `Py_single_input` enforces and what REPL
+/// front-ends rely on to know where one piece of typed-in input ends.
+fn parse_single_interactive_statement(
+    lxr: impl IntoIterator<Item = LexResult>,
+) -> Result<ast::Mod, ParseError> {
+    let mut lxr = lxr.into_iter().peekable();
+    let statement_tokens = take_one_statement(&mut lxr);
+    let tree = crate::python::parse_tokens(statement_tokens, Mode::Interactive)?;
+
+    match next_significant_token(&mut lxr) {
+        Some(Ok((location, tok, end_location))) => {
+            return Err(ParseError {
+                error: ParseErrorType::ExtraToken(tok),
+                location,
+                end_location,
+            });
+        }
+        Some(Err(err)) => {
+            return Err(ParseError {
+                error: ParseErrorType::Lexical(err.error),
+                location: err.location,
+                end_location: err.end_location,
+            });
+        }
+        None => {}
+    }
+
+    Ok(tree)
+}
+
+/// Collects tokens up to and including the end of the first top-level
+/// statement, leaving the rest of `lxr` untouched. A "statement" here is a
+/// full compound statement when it is one: `if x:\n    y\n` is a single
+/// unit, not just `if x : NEWLINE` with its suite left over, because a
+/// `Newline` only ends a statement when both bracket nesting and suite
+/// (`Indent`/`Dedent`) nesting are back at zero.
+///
+/// Concretely: a `Newline` at nesting-depth zero is a boundary unless the
+/// very next token is `Indent` (the header line of a compound statement
+/// looks depth-zero at that point, because the lexer hasn't emitted the
+/// `Indent` for its suite yet), or the statement began with a decorator
+/// (`@`) and its `def`/`class` hasn't been reached yet; and closing the
+/// outermost `Indent` with a matching `Dedent` is a boundary unless the next
+/// token is `elif`/`else`/`except`/`finally`, which continue the same
+/// compound statement with another clause.
+fn take_one_statement(
+    lxr: &mut core::iter::Peekable<impl Iterator<Item = LexResult>>,
+) -> Vec<LexResult> {
+    let mut depth = 0i32;
+    let mut suite_depth = 0i32;
+    let mut pending_decorator = false;
+    let mut collected = Vec::new();
+    while let Some(result) = lxr.next() {
+        let mut closed_outermost_suite = false;
+        if let Ok((_, tok, _)) = &result {
+            if collected.is_empty() && *tok == Tok::At {
+                pending_decorator = true;
+            }
+            match tok {
+                Tok::Def | Tok::Class => pending_decorator = false,
+                Tok::Lpar | Tok::Lsqb | Tok::Lbrace => depth += 1,
+                Tok::Rpar | Tok::Rsqb | Tok::Rbrace => depth -= 1,
+                Tok::Indent => suite_depth += 1,
+                Tok::Dedent => {
+                    suite_depth -= 1;
+                    closed_outermost_suite = suite_depth <= 0;
+                }
+                _ => {}
+            }
+        }
+        let is_newline = matches!(&result, Ok((_, Tok::Newline, _)));
+        collected.push(result);
+
+        if closed_outermost_suite && depth <= 0 {
+            let next_clause = matches!(
+                lxr.peek(),
+                Some(Ok((_, Tok::Elif | Tok::Else | Tok::Except | Tok::Finally, _)))
+            );
+            if !next_clause {
+                break;
+            }
+        }
+        if is_newline && depth <= 0 && suite_depth <= 0 {
+            let opens_suite = matches!(lxr.peek(), Some(Ok((_, Tok::Indent, _))));
+            if !opens_suite && !pending_decorator {
+                break;
+            }
+        }
+    }
+    collected
+}
+
+/// The first result left in `lxr` that isn't just end-of-input noise
+/// (`Newline`/`EndOfFile`), if any. A lexical error among the leftovers is
+/// returned too, so trailing garbage that doesn't even lex is rejected.
+fn next_significant_token(
+    lxr: &mut core::iter::Peekable<impl Iterator<Item = LexResult>>,
+) -> Option<LexResult> {
+    for result in lxr {
+        match result {
+            Ok((_, Tok::Newline, _)) | Ok((_, Tok::EndOfFile, _)) => continue,
+            other => return Some(other),
+        }
+    }
+    None
+}